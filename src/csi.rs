@@ -0,0 +1,28 @@
+use std::fmt;
+
+// Minimal set of ANSI/VT100 control sequences the editor needs to redraw the screen.
+pub enum Csi {
+    HideCursor,
+    ShowCursor,
+    CursorToTopLeft,
+    CursorTo(usize, usize),
+    ClearLine,
+    BackgroundColor(u8),
+    Color(u8),
+    ResetStyle,
+}
+
+impl fmt::Display for Csi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Csi::HideCursor => write!(f, "\x1b[?25l"),
+            Csi::ShowCursor => write!(f, "\x1b[?25h"),
+            Csi::CursorToTopLeft => write!(f, "\x1b[H"),
+            Csi::CursorTo(row, col) => write!(f, "\x1b[{};{}H", row, col),
+            Csi::ClearLine => write!(f, "\x1b[K"),
+            Csi::BackgroundColor(color) => write!(f, "\x1b[48;5;{}m", color),
+            Csi::Color(color) => write!(f, "\x1b[38;5;{}m", color),
+            Csi::ResetStyle => write!(f, "\x1b[0m"),
+        }
+    }
+}
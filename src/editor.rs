@@ -1,23 +1,40 @@
 use std::fs::File;
-use std::io::{self, stdin, stdout, BufRead, BufReader, Read, Stdout, Write};
-use std::{usize, vec};
+use std::io::{self, stdin, stdout, Read, Stdout, Write};
+use std::time::{Duration, Instant};
+use std::vec;
+
+use ropey::Rope;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::csi;
+use crate::highlighting::{self, Highlight};
 use crate::row::{self, EditorRow};
 use crate::{key, position::Position, window};
 
+const STATUS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct Editor {
     stdout: Stdout,
     append_buffer: Vec<u8>,
     cursor_position: Position,
     render_cursor_position: Position,
-    rows: Vec<EditorRow>,
+    // The document is kept as a single rope rather than a `Vec` of per-line buffers, so that
+    // inserting/removing text and joining/splitting lines are O(log n) regardless of file size.
+    // Visible `EditorRow`s (render + highlight) are derived on demand in `draw_rows`.
+    document: Rope,
     offset: Position,
     window_size: Position,
     status_message: String,
+    status_message_time: Instant,
     current_file_name: String,
     is_dirty: bool,
     e_key_history: Vec<key::EditorKey>,
+    filetype: Option<&'static highlighting::Filetype>,
+    // (row, grapheme start, grapheme end) of the current incremental-search match
+    search_match: Option<(usize, usize, usize)>,
+    // Toggled with Ctrl-N: when on, every line but the cursor's own shows its distance from
+    // the cursor row instead of its absolute number.
+    relative_numbers: bool,
 }
 
 impl Editor {
@@ -30,59 +47,42 @@ impl Editor {
             append_buffer: Vec::new(),
             cursor_position: Position::new(0, 0),
             render_cursor_position: Position::new(0, 0),
-            rows: Vec::new(),
+            document: Rope::new(),
             offset: Position::new(0, 0),
             window_size: Position::new(window_size.0, window_size.1 - 2),
             status_message: "".to_string(),
+            status_message_time: Instant::now(),
             current_file_name: "[NO NAME]".to_string(),
             is_dirty: false,
             e_key_history: Vec::new(),
+            filetype: None,
+            search_match: None,
+            relative_numbers: false,
         }
     }
 
     pub fn open_file(&mut self, filename: &String) -> io::Result<()> {
-        let file = File::open(filename)?;
+        let contents = std::fs::read_to_string(filename)?;
         self.current_file_name = filename.to_owned();
-        for row in BufReader::new(file).lines() {
-            if let Ok(r) = row {
-                let line_with_lf = r.into_bytes();
-                self.insert_row(
-                    self.rows.len(),
-                    row::EditorRow {
-                        chars: line_with_lf,
-                        render: vec![],
-                    },
-                );
-            }
-        }
+        self.filetype = highlighting::detect(filename);
+        self.document = Rope::from_str(contents.strip_suffix('\n').unwrap_or(&contents));
         self.is_dirty = false;
         Ok(())
     }
 
-    pub fn open_empty(&mut self) {
-        self.insert_row(
-            0,
-            row::EditorRow {
-                chars: vec![],
-                render: vec![],
-            },
-        )
-    }
-
     fn save(&mut self) -> io::Result<()> {
-        if self.current_file_name == "[NO NAME]".to_string() {
+        if self.current_file_name == "[NO NAME]" {
             match self.save_prompt() {
                 Some(file_name) => self.current_file_name = String::from_utf8(file_name).unwrap(),
-                None => return Err(std::io::Error::new(io::ErrorKind::Other, "user canceled")),
+                None => return Err(io::Error::other("user canceled")),
             }
         }
 
         let mut file = File::create(&self.current_file_name)?;
-
-        for r in &mut self.rows {
-            r.chars.push(b'\n');
-            file.write_all(r.chars.as_slice())?;
+        for chunk in self.document.chunks() {
+            file.write_all(chunk.as_bytes())?;
         }
+        file.write_all(b"\n")?;
         self.is_dirty = false;
         Ok(())
     }
@@ -94,7 +94,10 @@ impl Editor {
         let mut ret_buf = vec![];
         loop {
             let mut buf = [0; 1];
-            stdin.read(&mut buf).unwrap();
+            // The raw-mode read timeout makes an idle read return no byte; wait for the next one.
+            if stdin.read(&mut buf).unwrap() == 0 {
+                continue;
+            }
 
             match buf[0] {
                 b'\r' => return Some(ret_buf),
@@ -102,18 +105,176 @@ impl Editor {
                 8 => ret_buf.truncate(ret_buf.len().saturating_sub(1)),
                 // press esc to exit
                 27 => return None,
-                // allow only ascii character
-                c @ 32..=126 => ret_buf.push(c),
+                // any printable byte, including the continuation bytes of a multi-byte
+                // UTF-8 filename typed at the prompt
+                c if c >= 32 && c != 127 => ret_buf.push(c),
                 _ => (),
             }
+            // `ret_buf` can end mid-codepoint while a multi-byte UTF-8 filename byte is still
+            // being typed; render what's decodable so far instead of panicking on the partial
+            // tail.
             self.set_status_message(format!(
                 "Save as: {}",
-                String::from_utf8(ret_buf.clone()).unwrap()
+                String::from_utf8_lossy(&ret_buf)
             ));
             self.refresh_screen();
         }
     }
 
+    fn line_count(&self) -> usize {
+        self.document.len_lines()
+    }
+
+    // A line's content without its trailing `\n` (ropey includes the terminator in `line()`).
+    fn row_bytes(&self, row: usize) -> Vec<u8> {
+        if row >= self.line_count() {
+            return Vec::new();
+        }
+        let mut text = self.document.line(row).to_string();
+        if text.ends_with('\n') {
+            text.pop();
+        }
+        text.into_bytes()
+    }
+
+    fn row_text(&self, row: usize) -> String {
+        String::from_utf8(self.row_bytes(row)).unwrap_or_default()
+    }
+
+    // Row length in grapheme clusters (what the cursor steps over), not bytes or chars —
+    // a multi-codepoint cluster like an accented letter or an emoji with a modifier is one
+    // cursor position.
+    fn row_grapheme_count(&self, row: usize) -> usize {
+        self.row_text(row).graphemes(true).count()
+    }
+
+    // Converts a grapheme index on `row` into the rope's (Unicode-scalar) char offset within
+    // that row, accounting for clusters made up of more than one `char`.
+    fn row_grapheme_to_char_offset(&self, row: usize, grapheme_idx: usize) -> usize {
+        self.row_text(row)
+            .graphemes(true)
+            .take(grapheme_idx)
+            .map(|g| g.chars().count())
+            .sum()
+    }
+
+    fn row_char_to_doc_char(&self, row: usize, grapheme_idx: usize) -> usize {
+        self.document.line_to_char(row) + self.row_grapheme_to_char_offset(row, grapheme_idx)
+    }
+
+    // Width of the line-number gutter: enough digits for the last line, plus one separator
+    // column, e.g. a 120-line file gets a 4-column gutter ("120 ").
+    fn gutter_width(&self) -> usize {
+        let digits = (self.line_count().max(1) as u32).ilog10() + 1;
+        digits as usize + 1
+    }
+
+    // Usable width for line content once the gutter is carved out of `window_size.x`.
+    fn text_width(&self) -> usize {
+        self.window_size.x.saturating_sub(self.gutter_width())
+    }
+
+    fn gutter_text(&self, file_row: usize, gutter_width: usize) -> String {
+        let number = if self.relative_numbers && file_row != self.cursor_position.y {
+            (file_row as i64 - self.cursor_position.y as i64).unsigned_abs() as usize
+        } else {
+            file_row + 1
+        };
+        format!("{:>width$} ", number, width = gutter_width.saturating_sub(1))
+    }
+
+    // Incremental search, reusing the prompt-loop pattern from `save_prompt`: read the query
+    // one byte at a time, re-scanning the document after every keystroke so the cursor tracks
+    // the first match. Arrow keys (sent as ESC `[` A/B/C/D sequences in raw mode) jump to the
+    // first match on the next/previous line that has one, wrapping around the document; a
+    // further match on the same line is not stepped to. Enter commits the jump; Esc restores
+    // the position search began at.
+    fn find(&mut self) {
+        let saved_cursor = self.cursor_position;
+        let saved_render_cursor = self.render_cursor_position;
+        let saved_offset = self.offset;
+
+        let mut query = String::new();
+        let mut last_match: Option<(usize, usize)> = None;
+        let mut direction: i32 = 1;
+        let mut stdin = stdin();
+
+        loop {
+            self.set_status_message(format!(
+                "Search (Esc to cancel, Arrows/Enter to navigate): {}",
+                query
+            ));
+            self.refresh_screen();
+
+            let mut buf = [0; 1];
+            // The raw-mode read timeout makes an idle read return no byte; wait for the next one.
+            if stdin.read(&mut buf).unwrap() == 0 {
+                continue;
+            }
+
+            match buf[0] {
+                b'\r' => break,
+                27 => {
+                    let mut seq = [0; 2];
+                    if stdin.read(&mut seq).unwrap_or(0) == 2 && seq[0] == b'[' {
+                        direction = match seq[1] {
+                            b'A' | b'D' => -1,
+                            _ => 1,
+                        };
+                    } else {
+                        self.cursor_position = saved_cursor;
+                        self.render_cursor_position = saved_render_cursor;
+                        self.offset = saved_offset;
+                        self.search_match = None;
+                        self.set_status_message("".to_string());
+                        return;
+                    }
+                }
+                8 | 127 => {
+                    query.pop();
+                    last_match = None;
+                }
+                c @ 32..=126 => {
+                    query.push(c as char);
+                    last_match = None;
+                }
+                _ => (),
+            }
+
+            if query.is_empty() {
+                self.search_match = None;
+                continue;
+            }
+
+            let rows_len = self.line_count();
+            let start_row = match last_match {
+                Some((row, _)) => (row as i64 + direction as i64).rem_euclid(rows_len as i64) as usize,
+                None => self.cursor_position.y,
+            };
+
+            let mut row = start_row;
+            for _ in 0..rows_len {
+                if let Ok(text) = String::from_utf8(self.row_bytes(row)) {
+                    if let Some(byte_col) = text.find(query.as_str()) {
+                        // `str::find` returns a byte offset; translate it to the grapheme
+                        // index `cursor_position.x` and `search_match` are measured in.
+                        let grapheme_start = text[..byte_col].graphemes(true).count();
+                        let grapheme_end = text[..byte_col + query.len()].graphemes(true).count();
+                        last_match = Some((row, grapheme_start));
+                        self.cursor_position.y = row;
+                        self.cursor_position.x = grapheme_start;
+                        self.search_match = Some((row, grapheme_start, grapheme_end));
+                        break;
+                    }
+                }
+                row = (row as i64 + direction as i64).rem_euclid(rows_len as i64) as usize;
+            }
+        }
+
+        self.search_match = None;
+        self.set_status_message("".to_string());
+    }
+
     pub fn process_keypress(&mut self, key: &key::EditorKey) -> bool {
         let mut allow_exit = false;
         match key {
@@ -123,14 +284,12 @@ impl Editor {
             key::EditorKey::PageUp => self.cursor_position.y = self.offset.y,
             key::EditorKey::PageDown => {
                 self.cursor_position.y = self.offset.y + self.window_size.y - 1;
-                if self.cursor_position.y > self.rows.len() {
-                    self.cursor_position.y = self.rows.len()
+                if self.cursor_position.y > self.line_count() {
+                    self.cursor_position.y = self.line_count()
                 };
             }
-            key::EditorKey::End => {
-                if self.cursor_position.y == self.rows.len() {
-                    self.cursor_position.x = self.rows[self.cursor_position.y].chars.len()
-                };
+            key::EditorKey::End if self.cursor_position.y == self.line_count() => {
+                self.cursor_position.x = self.row_grapheme_count(self.cursor_position.y)
             }
             key::EditorKey::Ctrl(b'Q') => {
                 if self.is_dirty {
@@ -152,43 +311,45 @@ impl Editor {
                 Ok(()) => self.set_status_message("Written to disk".to_string()),
                 Err(e) => self.set_status_message(e.to_string()),
             },
+            key::EditorKey::Ctrl(b'F') => self.find(),
+            key::EditorKey::Ctrl(b'N') => self.relative_numbers = !self.relative_numbers,
             key::EditorKey::Ctrl(b'L') => (),
             key::EditorKey::Ctrl(b'H') => (),
             key::EditorKey::Null => return false,
             _ => (),
         }
-        self.e_key_history.push(key.clone());
+        self.e_key_history.push(*key);
         allow_exit
     }
 
     pub fn move_cursor(&mut self, key: &key::EditorKey) {
         let limit_x;
         let limit_y;
-        if self.rows.len() == 0 {
+        if self.line_count() == 0 {
             limit_x = 0;
             limit_y = 0;
         } else {
-            limit_x = if self.cursor_position.y == self.rows.len() {
+            limit_x = if self.cursor_position.y == self.line_count() {
                 0
             } else {
-                self.rows[self.cursor_position.y].chars.len()
+                self.row_grapheme_count(self.cursor_position.y)
             };
 
-            limit_y = self.rows.len() - 1;
+            limit_y = self.line_count() - 1;
         }
 
-        match key {
-            &key::EditorKey::ArrowLeft => {
+        match *key {
+            key::EditorKey::ArrowLeft => {
                 if self.cursor_position.x == 0 {
                     if self.cursor_position.y > 0 {
                         self.cursor_position.y -= 1;
-                        self.cursor_position.x = self.rows[self.cursor_position.y].chars.len();
+                        self.cursor_position.x = self.row_grapheme_count(self.cursor_position.y);
                     }
                 } else {
                     self.cursor_position -= Position::new(1, 0);
                 }
             }
-            &key::EditorKey::ArrowRight => {
+            key::EditorKey::ArrowRight => {
                 if self.cursor_position.y >= limit_y && self.cursor_position.x >= limit_x {
                     return;
                 }
@@ -199,31 +360,23 @@ impl Editor {
                     self.cursor_position.x = 0;
                 }
             }
-            &key::EditorKey::ArrowUp => {
-                if self.rows.len() == self.cursor_position.y {
+            key::EditorKey::ArrowUp => {
+                if self.line_count() == self.cursor_position.y {
                     self.cursor_position -= Position::new(0, 1);
                     return;
                 }
-                if self.cursor_position.x
-                    > self.rows[self.cursor_position.y.saturating_sub(1)]
-                        .chars
-                        .len()
-                {
-                    self.cursor_position.x = self.rows[self.cursor_position.y.saturating_sub(1)]
-                        .chars
-                        .len();
+                if self.cursor_position.x > self.row_grapheme_count(self.cursor_position.y.saturating_sub(1)) {
+                    self.cursor_position.x = self.row_grapheme_count(self.cursor_position.y.saturating_sub(1));
                     self.cursor_position -= Position::new(0, 1);
                     return;
                 }
                 self.cursor_position -= Position::new(0, 1);
             }
-            &key::EditorKey::ArrowDown => {
-                if self.cursor_position.y < limit_y {
-                    self.cursor_position.y += 1;
-                    if self.cursor_position.x > self.rows[self.cursor_position.y].chars.len() {
-                        self.cursor_position.x = self.rows[self.cursor_position.y].chars.len();
-                    }
-                };
+            key::EditorKey::ArrowDown if self.cursor_position.y < limit_y => {
+                self.cursor_position.y += 1;
+                if self.cursor_position.x > self.row_grapheme_count(self.cursor_position.y) {
+                    self.cursor_position.x = self.row_grapheme_count(self.cursor_position.y);
+                }
             }
             _ => (),
         }
@@ -237,7 +390,7 @@ impl Editor {
         self.draw_status_bar();
         self.append_buffer.append(&mut csi::Csi::CursorTo(
                 self.cursor_position.y - self.offset.y + 1,
-                self.render_cursor_position.x - self.offset.x + 1
+                self.render_cursor_position.x - self.offset.x + 1 + self.gutter_width()
                 ).to_string().into_bytes()
         );
         self.append_buffer.append(&mut csi::Csi::ShowCursor.to_string().into_bytes());
@@ -249,61 +402,88 @@ impl Editor {
     }
 
     pub fn draw_rows(&mut self) {
+        let gutter_width = self.gutter_width();
+        let text_width = self.text_width();
         for i in 0..self.window_size.y {
             self.append_buffer.append(&mut csi::Csi::ClearLine.to_string().into_bytes());
             let file_row = i + self.offset.y;
-            if file_row >= self.rows.len() {
+            if file_row >= self.line_count() {
+                self.append_buffer.append(&mut vec![b' '; gutter_width]);
                 self.append_buffer.append(&mut csi::Csi::BackgroundColor(236).to_string().into_bytes());
                 self.append_buffer.append(&mut "~".to_string().into_bytes());
                 self.append_buffer.append(&mut csi::Csi::ResetStyle.to_string().into_bytes());
-                
-                if i >= self.rows.len() {
-                    if self.rows.len() == 1
-                        && self.rows[0].chars.len() == 0
-                        && i == self.window_size.y / 3
-                    {
-                        let message = format!("riko editor -- version 0.0.1");
-                        let padding = (self.window_size.x - message.len()) / 2;
-                        for _ in 0..padding {
-                            self.append_buffer.push(b' ');
-                        }
-                        self.append_buffer.append(message.into_bytes().as_mut());
+
+                if self.line_count() == 1 && self.row_grapheme_count(0) == 0 && i == self.window_size.y / 3 {
+                    let message = "riko editor -- version 0.0.1".to_string();
+                    let padding = (text_width.saturating_sub(message.len())) / 2;
+                    for _ in 0..padding {
+                        self.append_buffer.push(b' ');
                     }
-                } else {
-                    self.append_buffer.append(&mut self.rows[i].chars.clone());
+                    self.append_buffer.append(message.into_bytes().as_mut());
                 }
             } else {
-                let mut len = self.rows[file_row]
-                    .render
-                    .len()
-                    .saturating_sub(self.offset.x);
-                if len > self.window_size.x {
-                    len = self.window_size.x
+                self.append_buffer
+                    .append(&mut self.gutter_text(file_row, gutter_width).into_bytes());
+
+                let mut row = EditorRow::new(self.row_bytes(file_row), self.filetype);
+                if let Some((match_row, char_start, char_end)) = self.search_match {
+                    if match_row == file_row {
+                        let render_start = row::render_position(&row.chars, char_start);
+                        let render_end = row::render_position(&row.chars, char_end).min(row.highlight.len());
+                        for h in row.highlight[render_start..render_end].iter_mut() {
+                            *h = Highlight::Match;
+                        }
+                    }
+                }
+
+                let mut len = row.render.len().saturating_sub(self.offset.x);
+                if len > text_width {
+                    len = text_width
                 }
 
                 let end = self.offset.x + len;
-                self.append_buffer.append(
-                    self.rows[file_row].render[self.offset.x..end]
-                        .to_vec()
-                        .as_mut(),
-                );
+                let mut current_highlight = Highlight::Normal;
+                for col in self.offset.x..end {
+                    let highlight = row.highlight[col];
+                    if highlight != current_highlight {
+                        self.append_buffer
+                            .append(&mut Self::highlight_csi(highlight).into_bytes());
+                        current_highlight = highlight;
+                    }
+                    self.append_buffer.extend_from_slice(row.render[col].as_bytes());
+                }
+                if current_highlight != Highlight::Normal {
+                    self.append_buffer.append(&mut csi::Csi::ResetStyle.to_string().into_bytes());
+                }
             }
             self.append_buffer.append(b"\r\n".to_vec().as_mut());
         }
     }
 
+    fn highlight_csi(highlight: Highlight) -> String {
+        match highlight {
+            Highlight::Normal => csi::Csi::ResetStyle.to_string(),
+            Highlight::Number => csi::Csi::Color(208).to_string(),
+            Highlight::String => csi::Csi::Color(114).to_string(),
+            Highlight::Comment => csi::Csi::Color(245).to_string(),
+            Highlight::Keyword => csi::Csi::Color(81).to_string(),
+            Highlight::Match => csi::Csi::BackgroundColor(226).to_string(),
+        }
+    }
+
     fn draw_status_bar(&mut self) {
         self.append_buffer.append(&mut csi::Csi::BackgroundColor(245).to_string().into_bytes());
 
         let mut status_text = format!(
-            "{}{}: (cx{}, cy{}): (rcx{}, rcy{}): lc:{}",
+            "{}{} [{}]: (cx{}, cy{}): (rcx{}, rcy{}): lc:{}",
             self.current_file_name,
             if self.is_dirty { "(modified)" } else { "" },
+            self.filetype.map(|ft| ft.name).unwrap_or("no ft"),
             self.cursor_position.x,
             self.cursor_position.y,
             self.render_cursor_position.x,
             self.render_cursor_position.y,
-            self.rows.len(),
+            self.line_count(),
         );
 
         // padding
@@ -315,19 +495,22 @@ impl Editor {
         self.append_buffer.append(&mut csi::Csi::ResetStyle.to_string().into_bytes());
         self.append_buffer.append(b"\r\n".to_vec().as_mut());
         self.append_buffer.append(&mut csi::Csi::ClearLine.to_string().into_bytes());
-        self.append_buffer
-            .append(self.status_message.as_bytes().to_vec().as_mut());
+        if self.status_message_time.elapsed() < STATUS_MESSAGE_TIMEOUT {
+            self.append_buffer
+                .append(self.status_message.as_bytes().to_vec().as_mut());
+        }
     }
 
     pub fn set_status_message(&mut self, message: String) {
         self.status_message = message;
+        self.status_message_time = Instant::now();
     }
 
     fn scroll(&mut self) {
         self.render_cursor_position.x = 0;
-        if self.cursor_position.y < self.rows.len() {
-            self.render_cursor_position.x =
-                self.rows[self.cursor_position.y].render_position(self.cursor_position.x);
+        if self.cursor_position.y < self.line_count() {
+            let chars = self.row_bytes(self.cursor_position.y);
+            self.render_cursor_position.x = row::render_position(&chars, self.cursor_position.x);
         }
         if self.cursor_position.y < self.offset.y {
             self.offset.y = self.cursor_position.y;
@@ -338,47 +521,25 @@ impl Editor {
         if self.render_cursor_position.x < self.offset.x {
             self.offset.x = self.render_cursor_position.x;
         }
-        if self.render_cursor_position.x >= self.offset.x + self.window_size.x {
-            self.offset.x = self.render_cursor_position.x - self.window_size.x + 1;
+        if self.render_cursor_position.x >= self.offset.x + self.text_width() {
+            self.offset.x = self.render_cursor_position.x - self.text_width() + 1;
         }
     }
 
-    fn insert_row(&mut self, at: usize, row: EditorRow) {
-        self.rows.insert(at, row);
-        self.rows[at].update();
-        self.is_dirty = true;
-    }
-
     fn insert_newline(&mut self) {
-        if self.rows[self.cursor_position.y].chars.len() == 0 {
-            self.insert_row(
-                self.cursor_position.y + 1,
-                EditorRow {
-                    chars: vec![],
-                    render: vec![],
-                },
-            );
-        } else if self.cursor_position.x == self.rows[self.cursor_position.y].chars.len() {
-            self.insert_row(
-                self.cursor_position.y + 1,
-                EditorRow {
-                    chars: vec![],
-                    render: vec![],
-                },
-            );
-        } else {
-            let r = self.rows[self.cursor_position.y].split(self.cursor_position.x);
-            if r.chars.len() != 0 {
-                self.rows[self.cursor_position.y].update();
-                self.insert_row(self.cursor_position.y + 1, r);
-            }
-        }
+        let idx = self.row_char_to_doc_char(self.cursor_position.y, self.cursor_position.x);
+        self.document.insert_char(idx, '\n');
         self.cursor_position.x = 0;
         self.cursor_position.y += 1;
+        self.is_dirty = true;
     }
 
-    fn insert_char(&mut self, char: u8) {
-        self.rows[self.cursor_position.y].insert_char(char, self.cursor_position.x);
+    // `char` now holds a full Unicode scalar value, decoded from the raw input bytes by the
+    // key-reading layer before `EditorKey::Char` is constructed, so multibyte characters are
+    // inserted as a single edit instead of corrupting the buffer byte by byte.
+    fn insert_char(&mut self, char: char) {
+        let idx = self.row_char_to_doc_char(self.cursor_position.y, self.cursor_position.x);
+        self.document.insert_char(idx, char);
         self.cursor_position.x += 1;
         self.render_cursor_position.x += 1;
         self.is_dirty = true;
@@ -386,19 +547,29 @@ impl Editor {
 
     fn backspace(&mut self) {
         if self.cursor_position.x >= 1 {
-            self.rows[self.cursor_position.y].delete_char(self.cursor_position.x.saturating_sub(1));
-            self.cursor_position.x = self.cursor_position.x.saturating_sub(1);
-            self.render_cursor_position.x =
-                self.rows[self.cursor_position.y].render_position(self.cursor_position.x);
+            // A grapheme cluster (e.g. a letter plus combining accents) can span more than one
+            // `char`, so delete the whole cluster rather than a single Unicode scalar value.
+            let removed_chars = self
+                .row_text(self.cursor_position.y)
+                .graphemes(true)
+                .nth(self.cursor_position.x - 1)
+                .map(|g| g.chars().count())
+                .unwrap_or(1);
+            let start = self.row_char_to_doc_char(self.cursor_position.y, self.cursor_position.x - 1);
+            self.document.remove(start..start + removed_chars);
+            self.cursor_position.x -= 1;
+            let chars = self.row_bytes(self.cursor_position.y);
+            self.render_cursor_position.x = row::render_position(&chars, self.cursor_position.x);
         } else {
             if self.cursor_position.y == 0 {
                 return;
             }
-            let mut mv = self.rows.remove(self.cursor_position.y);
+            let prev_len = self.row_grapheme_count(self.cursor_position.y - 1);
+            // Remove the `\n` that joins the previous line to this one.
+            let join_idx = self.document.line_to_char(self.cursor_position.y) - 1;
+            self.document.remove(join_idx..join_idx + 1);
             self.cursor_position.y -= 1;
-            self.cursor_position.x = self.rows[self.cursor_position.y].chars.len();
-            self.rows[self.cursor_position.y].append(&mut mv);
-            self.rows[self.cursor_position.y].update();
+            self.cursor_position.x = prev_len;
         }
         self.is_dirty = true;
     }
@@ -0,0 +1,151 @@
+// Syntax highlighting: classifies a row's grapheme clusters into `Highlight` classes so
+// `Editor::draw_rows` can colorize them, and a small per-extension keyword/comment table so
+// that classification is filetype aware.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Highlight {
+    Normal,
+    Number,
+    String,
+    Comment,
+    Keyword,
+    Match,
+}
+
+pub struct Filetype {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+    pub keywords: &'static [&'static str],
+    pub comment_start: &'static str,
+}
+
+pub const FILETYPES: &[Filetype] = &[
+    Filetype {
+        name: "rust",
+        extensions: &[".rs"],
+        keywords: &[
+            "fn", "let", "mut", "if", "else", "match", "struct", "enum", "impl", "pub", "return",
+            "for", "while", "loop", "use", "mod", "self", "Self", "true", "false", "const",
+            "static",
+        ],
+        comment_start: "//",
+    },
+    Filetype {
+        name: "c",
+        extensions: &[".c", ".h"],
+        keywords: &[
+            "int", "char", "void", "if", "else", "for", "while", "return", "struct", "typedef",
+            "static", "const", "switch", "case", "break",
+        ],
+        comment_start: "//",
+    },
+    Filetype {
+        name: "python",
+        extensions: &[".py"],
+        keywords: &[
+            "def", "class", "if", "elif", "else", "for", "while", "return", "import", "from",
+            "as", "with", "try", "except", "pass", "None", "True", "False",
+        ],
+        comment_start: "#",
+    },
+];
+
+pub fn detect(filename: &str) -> Option<&'static Filetype> {
+    FILETYPES
+        .iter()
+        .find(|ft| ft.extensions.iter().any(|ext| filename.ends_with(ext)))
+}
+
+// Every keyword/comment marker this table can contain is plain ASCII, so a non-ASCII grapheme
+// (more than one byte) can never be a separator.
+fn is_separator(g: &str) -> bool {
+    match g.as_bytes() {
+        [b] => b.is_ascii_whitespace() || b",.()+-/*=~%<>[];{}:&|!\"'".contains(b),
+        _ => false,
+    }
+}
+
+// Whether the grapheme sequence at the start of `graphemes` spells out the (ASCII) string `s`.
+fn starts_with(graphemes: &[&str], s: &str) -> bool {
+    let mut matched = 0;
+    for (g, expected) in graphemes.iter().zip(s.chars()) {
+        if *g != expected.to_string() {
+            return false;
+        }
+        matched += 1;
+    }
+    matched == s.chars().count()
+}
+
+pub fn highlight_row(graphemes: &[&str], filetype: Option<&Filetype>) -> Vec<Highlight> {
+    let mut hl = vec![Highlight::Normal; graphemes.len()];
+    let comment_start = filetype.map(|f| f.comment_start).unwrap_or("");
+    let mut in_string: Option<&str> = None;
+    let mut prev_sep = true;
+    let mut i = 0;
+
+    while i < graphemes.len() {
+        let g = graphemes[i];
+
+        if in_string.is_none() && !comment_start.is_empty() && starts_with(&graphemes[i..], comment_start) {
+            for slot in hl.iter_mut().skip(i) {
+                *slot = Highlight::Comment;
+            }
+            break;
+        }
+
+        if let Some(quote) = in_string {
+            hl[i] = Highlight::String;
+            if g == "\\" && i + 1 < graphemes.len() {
+                hl[i + 1] = Highlight::String;
+                i += 2;
+                continue;
+            }
+            if g == quote {
+                in_string = None;
+            }
+            i += 1;
+            prev_sep = false;
+            continue;
+        } else if g == "\"" || g == "'" {
+            in_string = Some(g);
+            hl[i] = Highlight::String;
+            i += 1;
+            continue;
+        }
+
+        let is_digit = g.chars().count() == 1 && g.chars().next().unwrap().is_ascii_digit();
+        let prev_is_number = i > 0 && hl[i - 1] == Highlight::Number;
+        if (is_digit && (prev_sep || prev_is_number)) || (g == "." && prev_is_number) {
+            hl[i] = Highlight::Number;
+            i += 1;
+            prev_sep = false;
+            continue;
+        }
+
+        if prev_sep {
+            if let Some(ft) = filetype {
+                let word = ft.keywords.iter().find(|kw| {
+                    starts_with(&graphemes[i..], kw)
+                        && graphemes
+                            .get(i + kw.chars().count())
+                            .is_none_or(|g| is_separator(g))
+                });
+                if let Some(word) = word {
+                    let len = word.chars().count();
+                    for slot in hl.iter_mut().skip(i).take(len) {
+                        *slot = Highlight::Keyword;
+                    }
+                    i += len;
+                    prev_sep = false;
+                    continue;
+                }
+            }
+        }
+
+        prev_sep = is_separator(g);
+        i += 1;
+    }
+
+    hl
+}
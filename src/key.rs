@@ -0,0 +1,132 @@
+use std::io::{self, Read, Stdin};
+
+#[derive(Clone, Copy, Debug)]
+pub enum EditorKey {
+    // Yielded when `read` times out with nothing waiting (see `terminal_io::EnableRawMode`)
+    // instead of blocking; lets `main`'s loop keep redrawing while the user is idle.
+    Null,
+    Char(char),
+    Enter,
+    BackSpace,
+    Ctrl(u8),
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    PageUp,
+    PageDown,
+    End,
+}
+
+pub trait ReadKey {
+    fn keys(self) -> Keys;
+}
+
+impl ReadKey for Stdin {
+    fn keys(self) -> Keys {
+        Keys { stdin: self }
+    }
+}
+
+pub struct Keys {
+    stdin: Stdin,
+}
+
+impl Keys {
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut buf = [0u8; 1];
+        match self.stdin.read(&mut buf)? {
+            0 => Ok(None),
+            _ => Ok(Some(buf[0])),
+        }
+    }
+
+    // Escape sequences arrive as `ESC [ <code>`, optionally followed by a `~` terminator for
+    // the keys vt100 can't express with a single trailing letter (page up/down).
+    fn read_escape_sequence(&mut self) -> io::Result<EditorKey> {
+        let b1 = match self.read_byte()? {
+            Some(b) => b,
+            None => return Ok(EditorKey::Null),
+        };
+        if b1 != b'[' {
+            return Ok(EditorKey::Null);
+        }
+        let b2 = match self.read_byte()? {
+            Some(b) => b,
+            None => return Ok(EditorKey::Null),
+        };
+        Ok(match b2 {
+            b'A' => EditorKey::ArrowUp,
+            b'B' => EditorKey::ArrowDown,
+            b'C' => EditorKey::ArrowRight,
+            b'D' => EditorKey::ArrowLeft,
+            b'F' => EditorKey::End,
+            b'4' => {
+                self.read_byte()?;
+                EditorKey::End
+            }
+            b'5' => {
+                self.read_byte()?;
+                EditorKey::PageUp
+            }
+            b'6' => {
+                self.read_byte()?;
+                EditorKey::PageDown
+            }
+            _ => EditorKey::Null,
+        })
+    }
+
+    // `first`'s high bits give the length of the UTF-8 sequence it leads; read the rest of the
+    // continuation bytes and decode the whole thing into one `char` so a multibyte keystroke
+    // reaches `Editor::insert_char` as a single edit instead of one raw byte at a time.
+    fn read_utf8_char(&mut self, first: u8) -> io::Result<char> {
+        let extra = if first >= 0xf0 {
+            3
+        } else if first >= 0xe0 {
+            2
+        } else {
+            1
+        };
+        let mut bytes = vec![first];
+        for _ in 0..extra {
+            match self.read_byte()? {
+                Some(b) => bytes.push(b),
+                None => break,
+            }
+        }
+        Ok(std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+impl Iterator for Keys {
+    type Item = io::Result<EditorKey>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let byte = match self.read_byte() {
+            Ok(Some(b)) => b,
+            Ok(None) => return Some(Ok(EditorKey::Null)),
+            Err(e) => return Some(Err(e)),
+        };
+
+        let key = match byte {
+            27 => match self.read_escape_sequence() {
+                Ok(k) => k,
+                Err(e) => return Some(Err(e)),
+            },
+            b'\r' => EditorKey::Enter,
+            8 | 127 => EditorKey::BackSpace,
+            1..=26 => EditorKey::Ctrl(byte | 0x40),
+            0x20..=0x7e => EditorKey::Char(byte as char),
+            0xc0..=0xff => match self.read_utf8_char(byte) {
+                Ok(c) => EditorKey::Char(c),
+                Err(e) => return Some(Err(e)),
+            },
+            _ => EditorKey::Null,
+        };
+        Some(Ok(key))
+    }
+}
@@ -10,6 +10,9 @@ mod window;
 mod sys;
 mod key;
 mod row;
+mod highlighting;
+mod csi;
+mod position;
 
 //const VERSION: &str = "0.0.1";
 
@@ -20,15 +23,19 @@ fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() >= 2 { 
         let filename = &args[1];
-        editor.open_file(&filename).unwrap();
+        editor.open_file(filename).unwrap();
     }
     
+    // `enable_raw_mode` sets a short read timeout, so `keys()` periodically yields
+    // `EditorKey::Null` even while idle; looping back round to `refresh_screen` on every
+    // iteration is what lets a stale status message clear itself without a keypress.
     for c in stdin().keys() {
-        if let Ok(key::EditorKey::Ctrl(113)) = c { 
+        let key = c.unwrap();
+        editor.move_cursor(&key);
+        if editor.process_keypress(&key) {
             t.suspend_raw_mode().unwrap();
-            process::exit(0); 
+            process::exit(0);
         }
-        editor.move_cursor(&c.unwrap());
         editor.refresh_screen();
     }
 }
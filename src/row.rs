@@ -0,0 +1,100 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::highlighting::{self, Filetype, Highlight};
+
+pub const KILO_TAB_STOP: usize = 8;
+
+// A rendering view of a single line, indexed by *display column* rather than by byte: `render`
+// holds each column's text (a single grapheme cluster, or one space for a tab-expanded column)
+// and `highlight` classifies that same column for `Editor::draw_rows`. Built on demand from the
+// document rope for whichever lines are currently visible.
+pub struct EditorRow {
+    pub chars: Vec<u8>,
+    pub render: Vec<String>,
+    pub highlight: Vec<Highlight>,
+}
+
+impl EditorRow {
+    pub fn new(chars: Vec<u8>, filetype: Option<&Filetype>) -> EditorRow {
+        let text = std::str::from_utf8(&chars).unwrap_or("");
+        let graphemes: Vec<&str> = text.graphemes(true).collect();
+        let grapheme_highlight = highlighting::highlight_row(&graphemes, filetype);
+
+        let mut render = Vec::with_capacity(graphemes.len());
+        let mut highlight = Vec::with_capacity(graphemes.len());
+        for (g, h) in graphemes.iter().zip(grapheme_highlight) {
+            if *g == "\t" {
+                let pad = KILO_TAB_STOP - (render.len() % KILO_TAB_STOP);
+                for _ in 0..pad {
+                    render.push(" ".to_string());
+                    highlight.push(h);
+                }
+            } else {
+                render.push((*g).to_string());
+                highlight.push(h);
+            }
+        }
+
+        EditorRow {
+            chars,
+            render,
+            highlight,
+        }
+    }
+}
+
+// Converts a grapheme-cluster index into the display column it lands on, i.e. the index into
+// this row's `render`/`highlight` once tabs are expanded to the next `KILO_TAB_STOP` multiple.
+// `chars` must be valid UTF-8; each grapheme (not byte or `char`) occupies one column except a
+// tab, which advances to the next tab stop.
+pub fn render_position(chars: &[u8], grapheme_x: usize) -> usize {
+    let text = std::str::from_utf8(chars).unwrap_or("");
+    let mut render_x = 0;
+    for g in text.graphemes(true).take(grapheme_x) {
+        if g == "\t" {
+            render_x += KILO_TAB_STOP - (render_x % KILO_TAB_STOP);
+        } else {
+            render_x += 1;
+        }
+    }
+    render_x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_position_counts_graphemes_not_bytes() {
+        // Five 2-byte "é" graphemes: grapheme index 5 is display column 5, not byte offset 10.
+        let chars = "ééééé".as_bytes();
+        assert_eq!(render_position(chars, 5), 5);
+    }
+
+    #[test]
+    fn render_position_expands_tabs_to_the_next_stop() {
+        let chars = "a\tb".as_bytes();
+        assert_eq!(render_position(chars, 1), 1); // before the tab
+        assert_eq!(render_position(chars, 2), KILO_TAB_STOP); // after the tab
+        assert_eq!(render_position(chars, 3), KILO_TAB_STOP + 1); // after "b"
+    }
+
+    #[test]
+    fn render_position_combines_multibyte_graphemes_and_tabs() {
+        // five "é" graphemes, then a tab, then "x" - the byte offset of "x" (11) must not
+        // be confused with its display column.
+        let chars = "ééééé\tx".as_bytes();
+        assert_eq!(render_position(chars, 6), KILO_TAB_STOP);
+        assert_eq!(render_position(chars, 7), KILO_TAB_STOP + 1);
+    }
+
+    #[test]
+    fn editor_row_render_is_column_indexed() {
+        let row = EditorRow::new("é\tx".as_bytes().to_vec(), None);
+        // "é" takes one column, the tab pads out to the next stop, then "x".
+        assert_eq!(row.render.len(), KILO_TAB_STOP + 1);
+        assert_eq!(row.render[0], "é");
+        assert_eq!(row.render[KILO_TAB_STOP], "x");
+        assert_eq!(row.highlight.len(), row.render.len());
+    }
+}
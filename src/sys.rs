@@ -0,0 +1,25 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+use libc::termios;
+
+// Thin wrapper around the termios get/set syscalls that `terminal_io` uses to enter and leave
+// raw mode.
+pub fn get_termios(fd: RawFd) -> io::Result<termios> {
+    unsafe {
+        let mut term: termios = std::mem::zeroed();
+        if libc::tcgetattr(fd, &mut term) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(term)
+    }
+}
+
+pub fn set_termios(fd: RawFd, term: &termios) -> io::Result<()> {
+    unsafe {
+        if libc::tcsetattr(fd, libc::TCSAFLUSH, term) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::io::{self, Stdout};
+use std::os::unix::io::AsRawFd;
+
+use libc::termios;
+
+use crate::sys;
+
+// Puts the terminal into raw mode for the lifetime of the returned guard: canonical mode, echo
+// and signal generation are disabled so every keystroke reaches `key::Keys` directly, and
+// `VMIN`/`VTIME` are set so a `read()` with nothing waiting returns after 100ms instead of
+// blocking forever. That's the periodic wakeup `main`'s loop relies on to re-run
+// `refresh_screen` (and clear a stale status message) even when the user isn't typing.
+pub struct RawMode {
+    fd: i32,
+    original: termios,
+}
+
+pub trait EnableRawMode {
+    fn enable_raw_mode(&self) -> io::Result<RawMode>;
+}
+
+impl EnableRawMode for Stdout {
+    fn enable_raw_mode(&self) -> io::Result<RawMode> {
+        let fd = self.as_raw_fd();
+        let original = sys::get_termios(fd)?;
+
+        let mut raw = original;
+        unsafe {
+            libc::cfmakeraw(&mut raw);
+        }
+        raw.c_cc[libc::VMIN] = 0;
+        raw.c_cc[libc::VTIME] = 1;
+        sys::set_termios(fd, &raw)?;
+
+        Ok(RawMode { fd, original })
+    }
+}
+
+impl RawMode {
+    pub fn suspend_raw_mode(&self) -> io::Result<()> {
+        sys::set_termios(self.fd, &self.original)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = self.suspend_raw_mode();
+    }
+}
@@ -0,0 +1,15 @@
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+// Queries the terminal for its size via the `TIOCGWINSZ` ioctl, the same mechanism `sys` uses
+// termios syscalls for. Returns `(columns, rows)`.
+pub fn get_size() -> io::Result<(usize, usize)> {
+    let stdout = io::stdout();
+    unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(stdout.as_raw_fd(), libc::TIOCGWINSZ, &mut size) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok((size.ws_col as usize, size.ws_row as usize))
+    }
+}